@@ -0,0 +1,45 @@
+//! Small in-process TTL cache for the raw HTTP bodies fetched from
+//! nba.com, keyed by request URL. Keeps `--serve` mode from re-hitting
+//! the CDN on every request when the underlying article/schedule JSON
+//! is known to change infrequently.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+pub struct Cache {
+    entries: Mutex<HashMap<String, (Instant, String)>>,
+}
+
+impl Cache {
+    pub fn new() -> Self {
+        Self {
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Returns the cached body for `key` if it was stored within `ttl`,
+    /// evicting it first if it has gone stale.
+    pub fn fresh(&self, key: &str, ttl: Duration) -> Option<String> {
+        let mut entries = self.entries.lock().unwrap();
+        match entries.get(key) {
+            Some((fetched_at, body)) if fetched_at.elapsed() < ttl => Some(body.clone()),
+            Some(_) => {
+                entries.remove(key);
+                None
+            }
+            None => None,
+        }
+    }
+
+    pub fn store(&self, key: String, body: String) {
+        let mut entries = self.entries.lock().unwrap();
+        entries.insert(key, (Instant::now(), body));
+    }
+}
+
+impl Default for Cache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
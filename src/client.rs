@@ -0,0 +1,346 @@
+//! Typed client surface for NBA.com: `NbaClient` owns the HTTP client and
+//! fetch cache, and exposes one handle per endpoint family (`articles`,
+//! `rankings`, `schedule`). Both the one-shot CLI path (`run`) and the
+//! `--serve` HTTP API build on top of this instead of talking to
+//! `reqwest` directly, so there's a single place that knows how to reach
+//! nba.com and how to recover from it being flaky.
+
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use reqwest::blocking::Client;
+use reqwest::header::{ACCEPT, ACCEPT_LANGUAGE};
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+
+use crate::cache::Cache;
+
+const CATEGORY_URL: &str = "https://www.nba.com/news/category/power-rankings";
+const SCHEDULE_URL: &str = "https://cdn.nba.com/static/json/staticData/scheduleLeagueV2.json";
+
+/// The category page and article pages are re-published a few times a
+/// day at most; the schedule JSON changes even less often within a day.
+const CATEGORY_TTL: Duration = Duration::from_secs(10 * 60);
+const ARTICLE_TTL: Duration = Duration::from_secs(10 * 60);
+const SCHEDULE_TTL: Duration = Duration::from_secs(60 * 60);
+
+/// Transient-failure retry policy shared by every endpoint handle.
+const MAX_ATTEMPTS: u32 = 3;
+const RETRY_BASE_DELAY: Duration = Duration::from_millis(200);
+
+/// Owns the underlying `reqwest` client and fetch cache. Cheap to share
+/// behind an `Arc` since both fields are internally synchronized.
+pub struct NbaClient {
+    http: Client,
+    cache: Cache,
+}
+
+impl NbaClient {
+    pub fn new() -> Result<Self> {
+        let http = Client::builder()
+            .user_agent(
+                "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 \
+                         (KHTML, like Gecko) Chrome/126.0.0.0 Safari/537.36",
+            )
+            .build()
+            .context("failed to build HTTP client")?;
+
+        Ok(Self {
+            http,
+            cache: Cache::new(),
+        })
+    }
+
+    pub fn articles(&self) -> ArticlesHandle<'_> {
+        ArticlesHandle { client: self }
+    }
+
+    pub fn rankings(&self) -> RankingsHandle<'_> {
+        RankingsHandle { client: self }
+    }
+
+    pub fn schedule(&self) -> ScheduleHandle<'_> {
+        ScheduleHandle { client: self }
+    }
+
+    /// Fetches `url` through the cache, keyed by the URL itself,
+    /// refreshing it over the network (with retries) once the cached
+    /// entry is older than `ttl`.
+    fn fetch_text_cached(&self, url: &str, ttl: Duration, accept: &'static str) -> Result<String> {
+        if let Some(body) = self.cache.fresh(url, ttl) {
+            return Ok(body);
+        }
+
+        let body = fetch_text_with_retry(|| {
+            self.http
+                .get(url)
+                .header(ACCEPT, accept)
+                .header(ACCEPT_LANGUAGE, "en-US,en;q=0.9")
+        })?;
+
+        self.cache.store(url.to_string(), body.clone());
+        Ok(body)
+    }
+}
+
+const HTML_ACCEPT: &str = "text/html,application/xhtml+xml,application/json";
+const JSON_ACCEPT: &str = "application/json";
+
+/// Looks up the slug of the most recently published power rankings
+/// article.
+pub struct ArticlesHandle<'a> {
+    client: &'a NbaClient,
+}
+
+impl ArticlesHandle<'_> {
+    pub fn latest_power_rankings_slug(&self) -> Result<String> {
+        let body = self
+            .client
+            .fetch_text_cached(CATEGORY_URL, CATEGORY_TTL, HTML_ACCEPT)
+            .context("failed to fetch power rankings category page")?;
+
+        let data: CategoryResponse = extract_next_data(&body)?;
+        data.props
+            .page_props
+            .category
+            .latest
+            .items
+            .into_iter()
+            .find_map(|item| {
+                let slug = item.slug.trim();
+                (!slug.is_empty()).then(|| slug.to_string())
+            })
+            .context("no articles found in power rankings category")
+    }
+}
+
+/// Fetches a single power rankings article by slug.
+pub struct RankingsHandle<'a> {
+    client: &'a NbaClient,
+}
+
+impl RankingsHandle<'_> {
+    pub fn fetch(&self, slug: &str) -> Result<PowerRankingsArticle> {
+        let url = format!("https://www.nba.com/news/{slug}");
+        let body = self
+            .client
+            .fetch_text_cached(&url, ARTICLE_TTL, HTML_ACCEPT)
+            .with_context(|| format!("failed to fetch power rankings article at {url}"))?;
+
+        let data: ArticleResponse = extract_next_data(&body)?;
+        let article = data.props.page_props.article;
+        let published_at = article
+            .published_date
+            .as_deref()
+            .and_then(|raw| DateTime::parse_from_rfc3339(raw).ok())
+            .map(|dt| dt.with_timezone(&Utc));
+
+        Ok(PowerRankingsArticle {
+            entries: article.power_rankings,
+            published_at,
+        })
+    }
+}
+
+/// The power-rankings article, plus the bits of article metadata other
+/// output formats (e.g. the RSS feed) need alongside the ranked teams.
+#[derive(Debug)]
+pub struct PowerRankingsArticle {
+    pub entries: Vec<PowerRankingEntry>,
+    pub published_at: Option<DateTime<Utc>>,
+}
+
+/// Fetches the league schedule.
+pub struct ScheduleHandle<'a> {
+    client: &'a NbaClient,
+}
+
+impl ScheduleHandle<'_> {
+    pub fn fetch(&self) -> Result<ScheduleResponse> {
+        let body = self
+            .client
+            .fetch_text_cached(SCHEDULE_URL, SCHEDULE_TTL, JSON_ACCEPT)
+            .context("failed to fetch league schedule")?;
+
+        serde_json::from_str(&body).context("failed to parse league schedule JSON")
+    }
+}
+
+/// Sends the request built by `make_request`, making up to
+/// `MAX_ATTEMPTS` attempts with jittered exponential backoff on connection
+/// errors, timeouts, 429s, and 5xxs. Other failures (4xx, parse errors)
+/// are not retried since a retry wouldn't change the outcome.
+fn fetch_text_with_retry(
+    make_request: impl Fn() -> reqwest::blocking::RequestBuilder,
+) -> Result<String> {
+    let mut last_err = None;
+
+    for attempt in 0..MAX_ATTEMPTS {
+        if attempt > 0 {
+            std::thread::sleep(retry_backoff(attempt));
+        }
+
+        match make_request().send() {
+            Ok(response) => match response.error_for_status() {
+                Ok(response) => {
+                    return response.text().context("failed to read response body");
+                }
+                Err(err) => {
+                    let retryable = err
+                        .status()
+                        .is_some_and(|status| status.as_u16() == 429 || status.is_server_error());
+                    last_err = Some(anyhow::Error::new(err).context("unsuccessful HTTP status code"));
+                    if !retryable {
+                        break;
+                    }
+                }
+            },
+            Err(err) => {
+                let retryable = err.is_timeout() || err.is_connect();
+                last_err = Some(anyhow::Error::new(err).context("HTTP request failed"));
+                if !retryable {
+                    break;
+                }
+            }
+        }
+    }
+
+    Err(last_err.unwrap_or_else(|| anyhow::anyhow!("request failed with no error recorded")))
+}
+
+fn retry_backoff(attempt: u32) -> Duration {
+    let backoff = RETRY_BASE_DELAY * 2u32.pow(attempt - 1);
+    let jitter_ms = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|since_epoch| since_epoch.subsec_millis() % 100)
+        .unwrap_or(0);
+    backoff + Duration::from_millis(jitter_ms as u64)
+}
+
+fn extract_next_data<T>(body: &str) -> Result<T>
+where
+    T: DeserializeOwned,
+{
+    let marker = "<script id=\"__NEXT_DATA__\" type=\"application/json\">";
+    let start = body
+        .find(marker)
+        .context("unable to locate __NEXT_DATA__ script tag")?
+        + marker.len();
+    let remainder = &body[start..];
+    let end = remainder
+        .find("</script>")
+        .context("unable to locate end of __NEXT_DATA__ script tag")?;
+    let json_str = &remainder[..end];
+    let data =
+        serde_json::from_str(json_str).context("failed to deserialize __NEXT_DATA__ JSON")?;
+    Ok(data)
+}
+
+#[derive(Deserialize)]
+struct CategoryResponse {
+    props: CategoryProps,
+}
+
+#[derive(Deserialize)]
+struct CategoryProps {
+    #[serde(rename = "pageProps")]
+    page_props: CategoryPageProps,
+}
+
+#[derive(Deserialize)]
+struct CategoryPageProps {
+    category: CategoryData,
+}
+
+#[derive(Deserialize)]
+struct CategoryData {
+    latest: LatestArticles,
+}
+
+#[derive(Deserialize)]
+struct LatestArticles {
+    items: Vec<ArticleItem>,
+}
+
+#[derive(Deserialize)]
+struct ArticleItem {
+    slug: String,
+}
+
+#[derive(Deserialize)]
+struct ArticleResponse {
+    props: ArticleProps,
+}
+
+#[derive(Deserialize)]
+struct ArticleProps {
+    #[serde(rename = "pageProps")]
+    page_props: ArticlePageProps,
+}
+
+#[derive(Deserialize)]
+struct ArticlePageProps {
+    #[serde(default)]
+    article: ArticleData,
+}
+
+#[derive(Deserialize, Default)]
+struct ArticleData {
+    #[serde(rename = "powerRankings", default)]
+    power_rankings: Vec<PowerRankingEntry>,
+    #[serde(rename = "publishedDate", default)]
+    published_date: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct PowerRankingEntry {
+    #[serde(rename = "teamId")]
+    pub team_id: Option<u32>,
+    #[serde(rename = "teamName")]
+    pub team_name: Option<String>,
+    #[serde(rename = "teamNickname")]
+    pub team_nickname: Option<String>,
+    #[serde(rename = "teamDisplayName")]
+    pub team_display_name: Option<String>,
+    #[serde(rename = "currentWeekRank")]
+    pub current_week_rank: Option<u32>,
+}
+
+#[derive(Deserialize)]
+pub struct ScheduleResponse {
+    #[serde(rename = "leagueSchedule")]
+    pub league_schedule: LeagueSchedule,
+}
+
+#[derive(Deserialize)]
+pub struct LeagueSchedule {
+    #[serde(rename = "gameDates")]
+    pub game_dates: Vec<GameDate>,
+}
+
+#[derive(Deserialize)]
+pub struct GameDate {
+    #[serde(rename = "games")]
+    pub games: Vec<Game>,
+}
+
+#[derive(Deserialize)]
+pub struct Game {
+    #[serde(rename = "gameDateUTC")]
+    pub game_date_utc: Option<String>,
+    #[serde(rename = "homeTeam")]
+    pub home_team: ScheduleTeam,
+    #[serde(rename = "awayTeam")]
+    pub away_team: ScheduleTeam,
+}
+
+#[derive(Deserialize)]
+pub struct ScheduleTeam {
+    #[serde(rename = "teamId")]
+    pub team_id: Option<u32>,
+    #[serde(rename = "teamCity")]
+    pub team_city: Option<String>,
+    #[serde(rename = "teamName")]
+    pub team_name: Option<String>,
+}
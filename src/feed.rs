@@ -0,0 +1,138 @@
+//! `--format rss` output: the power rankings rendered as an RSS 2.0
+//! feed, one `<item>` per weekly article, so a reader can subscribe
+//! instead of re-running the binary. The freshly fetched article gets
+//! its upcoming-game context from `upcoming_games_index`; older weeks
+//! come from `History` and only have the ranked team list, since their
+//! schedule snapshot was never saved.
+
+use std::collections::HashMap;
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use rss::{ChannelBuilder, ItemBuilder};
+
+use crate::history::WeeklySnapshot;
+use crate::rankings::{GameListing, ResolvedRanking};
+
+const CATEGORY_URL: &str = "https://www.nba.com/news/category/power-rankings";
+
+/// Acronyms that should stay all-caps instead of being title-cased.
+const ACRONYMS: &[&str] = &["nba"];
+
+/// Builds an RSS channel with one item for the latest power rankings
+/// article at `slug`, followed by one item per entry in `previous_weeks`
+/// (newest first).
+pub fn build_feed(
+    slug: &str,
+    published_at: Option<DateTime<Utc>>,
+    rankings: &[ResolvedRanking],
+    upcoming_games_index: &HashMap<u32, Vec<GameListing>>,
+    previous_weeks: &[WeeklySnapshot],
+) -> Result<String> {
+    let mut items = Vec::with_capacity(1 + previous_weeks.len());
+
+    items.push(build_item(
+        slug,
+        published_at,
+        rankings,
+        Some(upcoming_games_index),
+    ));
+
+    for week in previous_weeks {
+        let published_at = week.published_on.and_hms_opt(0, 0, 0).map(|dt| dt.and_utc());
+        items.push(build_item(&week.slug, published_at, &week.rankings, None));
+    }
+
+    let channel = ChannelBuilder::default()
+        .title("NBA Power Rankings")
+        .link(CATEGORY_URL)
+        .description("Weekly NBA power rankings with upcoming-game context.")
+        .items(items)
+        .build();
+
+    Ok(channel.to_string())
+}
+
+fn build_item(
+    slug: &str,
+    published_at: Option<DateTime<Utc>>,
+    rankings: &[ResolvedRanking],
+    upcoming_games_index: Option<&HashMap<u32, Vec<GameListing>>>,
+) -> rss::Item {
+    ItemBuilder::default()
+        .title(Some(headline_from_slug(slug)))
+        .link(Some(format!("https://www.nba.com/news/{slug}")))
+        .pub_date(published_at.map(|date| date.to_rfc2822()))
+        .description(Some(render_description(rankings, upcoming_games_index)))
+        .build()
+}
+
+/// Turns a URL slug like `"2024-25-nba-power-rankings-week-10"` into a
+/// readable headline.
+fn headline_from_slug(slug: &str) -> String {
+    slug.split('-')
+        .map(title_case_word)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn title_case_word(word: &str) -> String {
+    if ACRONYMS.contains(&word.to_lowercase().as_str()) {
+        return word.to_uppercase();
+    }
+
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().collect::<String>() + chars.as_str(),
+        None => String::new(),
+    }
+}
+
+fn render_description(
+    rankings: &[ResolvedRanking],
+    upcoming_games_index: Option<&HashMap<u32, Vec<GameListing>>>,
+) -> String {
+    rankings
+        .iter()
+        .map(|team| {
+            let mut line = format!("{}. {}", team.rank, team.team_name);
+            if let Some(next_game) = upcoming_games_index
+                .and_then(|index| index.get(&team.team_id))
+                .and_then(|games| games.first())
+            {
+                let location = if next_game.is_home { "vs" } else { "@" };
+                line.push_str(&format!(
+                    " (next: {} {} {})",
+                    next_game.date.format("%Y-%m-%d"),
+                    location,
+                    next_game.opponent
+                ));
+            }
+            line
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn headline_from_slug_title_cases_each_word() {
+        assert_eq!(
+            headline_from_slug("2024-25-nba-power-rankings-week-10"),
+            "2024 25 NBA Power Rankings Week 10"
+        );
+    }
+
+    #[test]
+    fn headline_from_slug_handles_a_single_word() {
+        assert_eq!(headline_from_slug("rankings"), "Rankings");
+    }
+
+    #[test]
+    fn headline_from_slug_handles_an_empty_slug() {
+        assert_eq!(headline_from_slug(""), "");
+    }
+}
@@ -0,0 +1,198 @@
+//! Week-over-week persistence for the resolved rankings, so `run()` can
+//! show whether a team moved up or down since the last snapshot.
+//!
+//! Snapshots are keyed by the article slug they came from, one row per
+//! team per slug, following the same "one row per entity per run"
+//! layout the schedule/article scrapers already assume.
+
+use anyhow::{Context, Result};
+use chrono::NaiveDate;
+use tokio_postgres::NoTls;
+
+use crate::rankings::ResolvedRanking;
+
+pub struct History {
+    client: tokio_postgres::Client,
+}
+
+impl History {
+    pub async fn connect(database_url: &str) -> Result<Self> {
+        let (client, connection) = tokio_postgres::connect(database_url, NoTls)
+            .await
+            .context("failed to connect to the history database")?;
+
+        tokio::spawn(async move {
+            if let Err(err) = connection.await {
+                eprintln!("history database connection error: {err}");
+            }
+        });
+
+        client
+            .batch_execute(
+                "CREATE TABLE IF NOT EXISTS ranking_snapshots (
+                    slug TEXT NOT NULL,
+                    published_on DATE NOT NULL,
+                    team_id INTEGER NOT NULL,
+                    team_name TEXT NOT NULL,
+                    rank INTEGER NOT NULL,
+                    PRIMARY KEY (slug, team_id)
+                )",
+            )
+            .await
+            .context("failed to create ranking_snapshots table")?;
+
+        Ok(Self { client })
+    }
+
+    /// Snapshots this week's rankings under `slug`, keyed by the
+    /// article's own `published_on` date (not the date this process
+    /// happens to run), so `latest_before` orders snapshots correctly
+    /// even if a week is back-filled or re-run out of order. Replaces
+    /// any rows already saved for that slug.
+    pub async fn save_snapshot(
+        &self,
+        slug: &str,
+        published_on: NaiveDate,
+        rankings: &[ResolvedRanking],
+    ) -> Result<()> {
+        self.client
+            .execute("DELETE FROM ranking_snapshots WHERE slug = $1", &[&slug])
+            .await
+            .context("failed to clear previous ranking snapshot")?;
+
+        for team in rankings {
+            self.client
+                .execute(
+                    "INSERT INTO ranking_snapshots (slug, published_on, team_id, team_name, rank)
+                     VALUES ($1, $2, $3, $4, $5)
+                     ON CONFLICT (slug, team_id)
+                     DO UPDATE SET
+                        published_on = EXCLUDED.published_on,
+                        rank = EXCLUDED.rank,
+                        team_name = EXCLUDED.team_name",
+                    &[
+                        &slug,
+                        &published_on,
+                        &(team.team_id as i32),
+                        &team.team_name,
+                        &(team.rank as i32),
+                    ],
+                )
+                .await
+                .context("failed to save ranking snapshot")?;
+        }
+        Ok(())
+    }
+
+    /// Returns the most recently published snapshot for a slug other
+    /// than `slug` and published strictly before `published_on`, i.e.
+    /// "last week's rankings" relative to this run. Filtering on
+    /// `published_on` (rather than just excluding `slug`) keeps this
+    /// correct when a week is back-filled or re-run out of order, since
+    /// it won't pick up a snapshot that's newer than the one being
+    /// processed.
+    pub async fn latest_before(
+        &self,
+        slug: &str,
+        published_on: NaiveDate,
+    ) -> Result<Vec<ResolvedRanking>> {
+        let rows = self
+            .client
+            .query(
+                "SELECT team_id, team_name, rank FROM ranking_snapshots
+                 WHERE slug = (
+                     SELECT slug FROM ranking_snapshots
+                     WHERE slug <> $1 AND published_on < $2
+                     ORDER BY published_on DESC
+                     LIMIT 1
+                 )",
+                &[&slug, &published_on],
+            )
+            .await
+            .context("failed to load previous ranking snapshot")?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| {
+                let team_id: i32 = row.get(0);
+                let team_name: String = row.get(1);
+                let rank: i32 = row.get(2);
+                ResolvedRanking {
+                    rank: rank as u32,
+                    team_id: team_id as u32,
+                    team_name,
+                    previous_rank: None,
+                    movement: None,
+                    schedule_difficulty: None,
+                }
+            })
+            .collect())
+    }
+
+    /// Returns up to `limit` of the most recently published weekly
+    /// snapshots, newest first, for use by the RSS feed (one `<item>`
+    /// per weekly article).
+    pub async fn recent_weeks(&self, limit: i64) -> Result<Vec<WeeklySnapshot>> {
+        let weeks = self
+            .client
+            .query(
+                "SELECT slug, published_on FROM ranking_snapshots
+                 GROUP BY slug, published_on
+                 ORDER BY published_on DESC
+                 LIMIT $1",
+                &[&limit],
+            )
+            .await
+            .context("failed to list recent ranking snapshot weeks")?;
+
+        let mut snapshots = Vec::with_capacity(weeks.len());
+        for week in weeks {
+            let slug: String = week.get(0);
+            let published_on: NaiveDate = week.get(1);
+
+            let rows = self
+                .client
+                .query(
+                    "SELECT team_id, team_name, rank FROM ranking_snapshots
+                     WHERE slug = $1
+                     ORDER BY rank",
+                    &[&slug],
+                )
+                .await
+                .context("failed to load ranking snapshot")?;
+
+            let rankings = rows
+                .into_iter()
+                .map(|row| {
+                    let team_id: i32 = row.get(0);
+                    let team_name: String = row.get(1);
+                    let rank: i32 = row.get(2);
+                    ResolvedRanking {
+                        rank: rank as u32,
+                        team_id: team_id as u32,
+                        team_name,
+                        previous_rank: None,
+                        movement: None,
+                        schedule_difficulty: None,
+                    }
+                })
+                .collect();
+
+            snapshots.push(WeeklySnapshot {
+                slug,
+                published_on,
+                rankings,
+            });
+        }
+
+        Ok(snapshots)
+    }
+}
+
+/// One weekly article's worth of rankings, as stored in
+/// `ranking_snapshots`, for rendering as a past RSS feed item.
+pub struct WeeklySnapshot {
+    pub slug: String,
+    pub published_on: NaiveDate,
+    pub rankings: Vec<ResolvedRanking>,
+}
@@ -0,0 +1,417 @@
+//! Domain model shared by the CLI output and the `--serve` HTTP API:
+//! resolving raw article entries into ranked teams, and matching those
+//! teams up against the league schedule.
+
+use std::collections::HashMap;
+
+use chrono::NaiveDate;
+use serde::Serialize;
+
+use crate::client::{PowerRankingEntry, ScheduleResponse, ScheduleTeam};
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ResolvedRanking {
+    pub rank: u32,
+    pub team_id: u32,
+    pub team_name: String,
+    /// The team's rank in the previous snapshot, if one was found.
+    pub previous_rank: Option<u32>,
+    /// A human-readable summary of the move since `previous_rank`, e.g.
+    /// `"▲3"`, `"▼1"`, `"—"`, or `"NEW"`. `None` when no history lookup
+    /// was performed at all (no `DATABASE_URL` configured).
+    pub movement: Option<String>,
+    /// Strength-of-schedule over the upcoming window, when computed.
+    pub schedule_difficulty: Option<ScheduleDifficulty>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct GameListing {
+    pub date: NaiveDate,
+    pub opponent: String,
+    pub opponent_team_id: Option<u32>,
+    pub is_home: bool,
+}
+
+impl GameListing {
+    pub fn new(date: NaiveDate, opponent: String, opponent_team_id: Option<u32>, is_home: bool) -> Self {
+        Self {
+            date,
+            opponent,
+            opponent_team_id,
+            is_home,
+        }
+    }
+}
+
+/// Average-opponent-rank strength-of-schedule score for a team's
+/// upcoming window. `average_opponent_rank` is only ever averaged over
+/// `ranked_opponents_count` of the `games_count` total upcoming games —
+/// the rest face opponents with no current rank to cross-reference.
+#[derive(Debug, Clone, Serialize)]
+pub struct ScheduleDifficulty {
+    pub games_count: usize,
+    pub ranked_opponents_count: usize,
+    pub average_opponent_rank: f64,
+    pub label: &'static str,
+}
+
+/// Resolves raw power-ranking entries into `ResolvedRanking`s, dropping
+/// entries that are missing a rank, team id, or usable team name, and
+/// sorting the remainder by rank.
+pub fn resolve_rankings(entries: Vec<PowerRankingEntry>) -> Vec<ResolvedRanking> {
+    let mut ranked: Vec<_> = entries
+        .into_iter()
+        .filter_map(|entry| {
+            let rank = entry.current_week_rank?;
+            let team_id = entry.team_id?;
+            let team_name = entry
+                .team_name
+                .or(entry.team_nickname.clone())
+                .or(entry.team_display_name.clone())?;
+            Some(ResolvedRanking {
+                rank,
+                team_id,
+                team_name,
+                previous_rank: None,
+                movement: None,
+                schedule_difficulty: None,
+            })
+        })
+        .collect();
+
+    ranked.sort_by_key(|r| r.rank);
+    ranked
+}
+
+/// Annotates `rankings` with `previous_rank`/`movement` by matching each
+/// team against its rank in `previous_ranks` (keyed by `team_id`). Teams
+/// absent from `previous_ranks` are marked `"NEW"`.
+pub fn apply_movement(rankings: &mut [ResolvedRanking], previous_ranks: &HashMap<u32, u32>) {
+    for ranking in rankings.iter_mut() {
+        ranking.previous_rank = previous_ranks.get(&ranking.team_id).copied();
+        ranking.movement = Some(match ranking.previous_rank {
+            Some(previous_rank) => movement_marker(previous_rank, ranking.rank),
+            None => "NEW".to_string(),
+        });
+    }
+}
+
+fn movement_marker(previous_rank: u32, rank: u32) -> String {
+    match previous_rank.cmp(&rank) {
+        std::cmp::Ordering::Greater => format!("▲{}", previous_rank - rank),
+        std::cmp::Ordering::Less => format!("▼{}", rank - previous_rank),
+        std::cmp::Ordering::Equal => "—".to_string(),
+    }
+}
+
+pub fn build_upcoming_games_index(
+    schedule: &ScheduleResponse,
+    start: NaiveDate,
+    end: NaiveDate,
+) -> HashMap<u32, Vec<GameListing>> {
+    let mut index: HashMap<u32, Vec<GameListing>> = HashMap::new();
+
+    for game_date in &schedule.league_schedule.game_dates {
+        for game in &game_date.games {
+            let Some(ref date_str) = game.game_date_utc else {
+                continue;
+            };
+            let Ok(date_time) = chrono::DateTime::parse_from_rfc3339(date_str) else {
+                continue;
+            };
+            let game_day = date_time.date_naive();
+
+            if game_day < start || game_day >= end {
+                continue;
+            }
+
+            let opponent_for_home = format_team(&game.away_team);
+            let opponent_for_away = format_team(&game.home_team);
+
+            if let Some(team_id) = game.home_team.team_id {
+                index.entry(team_id).or_default().push(GameListing::new(
+                    game_day,
+                    opponent_for_home.clone(),
+                    game.away_team.team_id,
+                    true,
+                ));
+            }
+
+            if let Some(team_id) = game.away_team.team_id {
+                index.entry(team_id).or_default().push(GameListing::new(
+                    game_day,
+                    opponent_for_away.clone(),
+                    game.home_team.team_id,
+                    false,
+                ));
+            }
+        }
+    }
+
+    for games in index.values_mut() {
+        games.sort_by_key(|game| game.date);
+    }
+
+    index
+}
+
+/// Scores each team's strength-of-schedule over its upcoming window: the
+/// average `current_week_rank` of opponents that are themselves in the
+/// rankings (opponents not currently ranked are skipped since they have
+/// no rank to cross-reference). Teams with no cross-referenceable
+/// opponents are left with `schedule_difficulty: None`.
+pub fn score_schedule(
+    rankings: &mut [ResolvedRanking],
+    upcoming_games_index: &HashMap<u32, Vec<GameListing>>,
+) {
+    let rank_by_team: HashMap<u32, u32> =
+        rankings.iter().map(|team| (team.team_id, team.rank)).collect();
+
+    for ranking in rankings.iter_mut() {
+        let Some(games) = upcoming_games_index.get(&ranking.team_id) else {
+            continue;
+        };
+
+        let opponent_ranks: Vec<u32> = games
+            .iter()
+            .filter_map(|game| game.opponent_team_id)
+            .filter_map(|team_id| rank_by_team.get(&team_id).copied())
+            .collect();
+
+        if opponent_ranks.is_empty() {
+            continue;
+        }
+
+        let average_opponent_rank =
+            opponent_ranks.iter().sum::<u32>() as f64 / opponent_ranks.len() as f64;
+
+        ranking.schedule_difficulty = Some(ScheduleDifficulty {
+            games_count: games.len(),
+            ranked_opponents_count: opponent_ranks.len(),
+            average_opponent_rank,
+            label: difficulty_label(average_opponent_rank),
+        });
+    }
+}
+
+fn difficulty_label(average_opponent_rank: f64) -> &'static str {
+    if average_opponent_rank <= 10.0 {
+        "hard"
+    } else if average_opponent_rank <= 20.0 {
+        "average"
+    } else {
+        "easy"
+    }
+}
+
+pub fn format_team(team: &ScheduleTeam) -> String {
+    match (&team.team_city, &team.team_name) {
+        (Some(city), Some(name)) if !city.is_empty() => format!("{city} {name}"),
+        (_, Some(name)) if !name.is_empty() => name.clone(),
+        _ => "TBD Opponent".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::{Game, GameDate, LeagueSchedule, ScheduleResponse, ScheduleTeam};
+
+    fn team(id: u32) -> ScheduleTeam {
+        ScheduleTeam {
+            team_id: Some(id),
+            team_city: Some("Boston".to_string()),
+            team_name: Some("Celtics".to_string()),
+        }
+    }
+
+    fn schedule_with_game(date_utc: &str, home_id: u32, away_id: u32) -> ScheduleResponse {
+        ScheduleResponse {
+            league_schedule: LeagueSchedule {
+                game_dates: vec![GameDate {
+                    games: vec![Game {
+                        game_date_utc: Some(date_utc.to_string()),
+                        home_team: team(home_id),
+                        away_team: team(away_id),
+                    }],
+                }],
+            },
+        }
+    }
+
+    fn window() -> (NaiveDate, NaiveDate) {
+        let start = NaiveDate::from_ymd_opt(2026, 7, 26).unwrap();
+        (start, start + chrono::Duration::days(7))
+    }
+
+    #[test]
+    fn includes_games_within_the_window() {
+        let schedule = schedule_with_game("2026-07-27T00:00:00Z", 1, 2);
+        let (start, end) = window();
+
+        let index = build_upcoming_games_index(&schedule, start, end);
+
+        let home_games = index.get(&1).expect("home team should have a game");
+        assert_eq!(home_games.len(), 1);
+        assert!(home_games[0].is_home);
+
+        let away_games = index.get(&2).expect("away team should have a game");
+        assert!(!away_games[0].is_home);
+    }
+
+    #[test]
+    fn excludes_games_on_or_after_the_window_end() {
+        let schedule = schedule_with_game("2026-08-02T00:00:00Z", 1, 2);
+        let (start, end) = window();
+
+        assert!(build_upcoming_games_index(&schedule, start, end).is_empty());
+    }
+
+    #[test]
+    fn excludes_games_before_the_window_start() {
+        let schedule = schedule_with_game("2026-07-20T00:00:00Z", 1, 2);
+        let (start, end) = window();
+
+        assert!(build_upcoming_games_index(&schedule, start, end).is_empty());
+    }
+
+    #[test]
+    fn empty_schedule_yields_an_empty_index() {
+        let schedule = ScheduleResponse {
+            league_schedule: LeagueSchedule { game_dates: vec![] },
+        };
+        let (start, end) = window();
+
+        assert!(build_upcoming_games_index(&schedule, start, end).is_empty());
+    }
+
+    #[test]
+    fn movement_marker_reports_a_rise() {
+        assert_eq!(movement_marker(7, 4), "▲3");
+    }
+
+    #[test]
+    fn movement_marker_reports_a_fall() {
+        assert_eq!(movement_marker(2, 5), "▼3");
+    }
+
+    #[test]
+    fn movement_marker_reports_no_change() {
+        assert_eq!(movement_marker(4, 4), "—");
+    }
+
+    #[test]
+    fn apply_movement_marks_unseen_teams_as_new() {
+        let mut rankings = vec![ResolvedRanking {
+            rank: 1,
+            team_id: 99,
+            team_name: "Unranked Last Week".to_string(),
+            previous_rank: None,
+            movement: None,
+            schedule_difficulty: None,
+        }];
+
+        apply_movement(&mut rankings, &HashMap::new());
+
+        assert_eq!(rankings[0].previous_rank, None);
+        assert_eq!(rankings[0].movement.as_deref(), Some("NEW"));
+    }
+
+    #[test]
+    fn apply_movement_carries_forward_previous_rank() {
+        let mut rankings = vec![ResolvedRanking {
+            rank: 2,
+            team_id: 1,
+            team_name: "Celtics".to_string(),
+            previous_rank: None,
+            movement: None,
+            schedule_difficulty: None,
+        }];
+        let previous_ranks = HashMap::from([(1, 5)]);
+
+        apply_movement(&mut rankings, &previous_ranks);
+
+        assert_eq!(rankings[0].previous_rank, Some(5));
+        assert_eq!(rankings[0].movement.as_deref(), Some("▲3"));
+    }
+
+    fn resolved(team_id: u32, rank: u32) -> ResolvedRanking {
+        ResolvedRanking {
+            rank,
+            team_id,
+            team_name: format!("Team {team_id}"),
+            previous_rank: None,
+            movement: None,
+            schedule_difficulty: None,
+        }
+    }
+
+    #[test]
+    fn score_schedule_averages_only_ranked_opponents() {
+        let mut rankings = vec![resolved(1, 1), resolved(2, 10), resolved(3, 20)];
+        let mut index = HashMap::new();
+        index.insert(
+            1,
+            vec![
+                GameListing::new(
+                    NaiveDate::from_ymd_opt(2026, 7, 27).unwrap(),
+                    "Team 2".to_string(),
+                    Some(2),
+                    true,
+                ),
+                GameListing::new(
+                    NaiveDate::from_ymd_opt(2026, 7, 29).unwrap(),
+                    "Unranked Opponent".to_string(),
+                    Some(999),
+                    false,
+                ),
+                GameListing::new(
+                    NaiveDate::from_ymd_opt(2026, 7, 31).unwrap(),
+                    "Team 3".to_string(),
+                    Some(3),
+                    true,
+                ),
+            ],
+        );
+
+        score_schedule(&mut rankings, &index);
+
+        let difficulty = rankings[0]
+            .schedule_difficulty
+            .as_ref()
+            .expect("team 1 should have a schedule difficulty");
+        assert_eq!(difficulty.games_count, 3);
+        assert_eq!(difficulty.ranked_opponents_count, 2);
+        assert_eq!(difficulty.average_opponent_rank, 15.0);
+        assert_eq!(difficulty.label, "average");
+    }
+
+    #[test]
+    fn score_schedule_leaves_teams_with_no_games_unscored() {
+        let mut rankings = vec![resolved(1, 1)];
+        let index = HashMap::new();
+
+        score_schedule(&mut rankings, &index);
+
+        assert!(rankings[0].schedule_difficulty.is_none());
+    }
+
+    #[test]
+    fn score_schedule_leaves_teams_with_no_ranked_opponents_unscored() {
+        let mut rankings = vec![resolved(1, 1)];
+        let mut index = HashMap::new();
+        index.insert(
+            1,
+            vec![GameListing::new(
+                NaiveDate::from_ymd_opt(2026, 7, 27).unwrap(),
+                "Unranked Opponent".to_string(),
+                Some(999),
+                true,
+            )],
+        );
+
+        score_schedule(&mut rankings, &index);
+
+        assert!(rankings[0].schedule_difficulty.is_none());
+    }
+}
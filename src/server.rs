@@ -0,0 +1,132 @@
+//! `--serve` mode: exposes the rankings + schedule pipeline as a small
+//! JSON API instead of printing to stdout, so other tools (a dashboard,
+//! a bot, whatever) can consume it directly.
+
+use std::sync::Arc;
+
+use anyhow::{Context, Result};
+use chrono::{Duration, Utc};
+use serde::Serialize;
+use warp::Filter;
+use warp::http::StatusCode;
+
+use crate::client::NbaClient;
+use crate::rankings::{self, ResolvedRanking};
+
+const DEFAULT_UPCOMING_DAYS: i64 = 7;
+
+pub fn serve(addr: ([u8; 4], u16)) -> Result<()> {
+    let runtime = tokio::runtime::Runtime::new().context("failed to start async runtime")?;
+    runtime.block_on(serve_async(addr))
+}
+
+async fn serve_async(addr: ([u8; 4], u16)) -> Result<()> {
+    let nba = Arc::new(NbaClient::new()?);
+
+    let nba_for_rankings = nba.clone();
+    let rankings = warp::path!("rankings")
+        .and(warp::get())
+        .and_then(move || fetch_blocking({
+            let nba = nba_for_rankings.clone();
+            move || handle_rankings(&nba)
+        }));
+
+    let nba_for_top = nba.clone();
+    let rankings_top = warp::path!("rankings" / "top" / usize)
+        .and(warp::get())
+        .and_then(move |n| fetch_blocking({
+            let nba = nba_for_top.clone();
+            move || handle_rankings_top(&nba, n)
+        }));
+
+    let nba_for_upcoming = nba.clone();
+    let upcoming_query = warp::query::<UpcomingQuery>();
+    let team_upcoming = warp::path!("teams" / u32 / "upcoming")
+        .and(warp::get())
+        .and(upcoming_query)
+        .and_then(move |team_id, query: UpcomingQuery| {
+            fetch_blocking({
+                let nba = nba_for_upcoming.clone();
+                move || handle_team_upcoming(&nba, team_id, query.days)
+            })
+        });
+
+    let routes = rankings.or(rankings_top).or(team_upcoming);
+
+    println!("listening on http://{}:{}", addr.0.map(|o| o.to_string()).join("."), addr.1);
+    warp::serve(routes).run(addr).await;
+    Ok(())
+}
+
+#[derive(serde::Deserialize)]
+struct UpcomingQuery {
+    #[serde(default = "default_upcoming_days")]
+    days: i64,
+}
+
+fn default_upcoming_days() -> i64 {
+    DEFAULT_UPCOMING_DAYS
+}
+
+/// Runs a blocking handler (the scrape functions use `reqwest::blocking`)
+/// on the blocking thread pool and turns `anyhow::Error` into a 502.
+async fn fetch_blocking<F, T>(handler: F) -> Result<impl warp::Reply, warp::Rejection>
+where
+    F: FnOnce() -> Result<T> + Send + 'static,
+    T: Serialize + Send + 'static,
+{
+    let result = tokio::task::spawn_blocking(handler)
+        .await
+        .map_err(|_| warp::reject::reject())?;
+
+    match result {
+        Ok(body) => Ok(warp::reply::with_status(
+            warp::reply::json(&body),
+            StatusCode::OK,
+        )),
+        Err(err) => {
+            eprintln!("error: {err:?}");
+            Ok(warp::reply::with_status(
+                warp::reply::json(&ErrorBody {
+                    error: err.to_string(),
+                }),
+                StatusCode::BAD_GATEWAY,
+            ))
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct ErrorBody {
+    error: String,
+}
+
+fn fetch_resolved_rankings(nba: &NbaClient) -> Result<Vec<ResolvedRanking>> {
+    let slug = nba.articles().latest_power_rankings_slug()?;
+    let article = nba.rankings().fetch(&slug)?;
+    Ok(rankings::resolve_rankings(article.entries))
+}
+
+fn handle_rankings(nba: &NbaClient) -> Result<Vec<ResolvedRanking>> {
+    fetch_resolved_rankings(nba)
+}
+
+fn handle_rankings_top(nba: &NbaClient, n: usize) -> Result<Vec<ResolvedRanking>> {
+    let mut ranked = fetch_resolved_rankings(nba)?;
+    ranked.truncate(n);
+    Ok(ranked)
+}
+
+fn handle_team_upcoming(
+    nba: &NbaClient,
+    team_id: u32,
+    days: i64,
+) -> Result<Vec<rankings::GameListing>> {
+    let schedule = nba.schedule().fetch()?;
+
+    let today = Utc::now().date_naive();
+    let cutoff = today + Duration::days(days);
+    let index = rankings::build_upcoming_games_index(&schedule, today, cutoff);
+
+    Ok(index.get(&team_id).cloned().unwrap_or_default())
+}